@@ -5,7 +5,7 @@ fn main() {
     App::new()
         .add_plugins(MinimalPlugins)
         .add_plugins(AssetPlugin::default())
-        .add_plugins(FusabiPlugin)
+        .add_plugins(FusabiPlugin::without_scripts_folder())
         .add_plugins(RunnerPlugin)
         .add_systems(Startup, setup)
         .run();
@@ -15,8 +15,5 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     let handle = asset_server.load("hello.fsx");
 
     // Spawn an entity that wants to run this script
-    commands.spawn(RunScript {
-        handle,
-        executed: false,
-    });
+    commands.spawn(RunScript::new(handle));
 }
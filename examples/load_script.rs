@@ -5,7 +5,7 @@ fn main() {
     App::new()
         .add_plugins(MinimalPlugins)
         .add_plugins(AssetPlugin::default())
-        .add_plugins(FusabiPlugin)
+        .add_plugins(FusabiPlugin::without_scripts_folder())
         .add_systems(Startup, setup)
         .add_systems(Update, check_asset)
         .run();
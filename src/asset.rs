@@ -18,14 +18,89 @@ pub struct FusabiScript {
     pub bytecode: Vec<u8>,
 }
 
-/// Metadata header for .fzb files
-#[derive(Serialize, Deserialize, Debug)]
+/// Magic number written at the start of every `.fzb` file: the ASCII bytes
+/// `FZB1` in little-endian order.
+pub const FUSABI_MAGIC: u32 = u32::from_le_bytes(*b"FZB1");
+
+/// Version of the bytecode layout this crate reads and writes.
+///
+/// Bump this whenever the linked `fusabi_vm` changes its serialized `Chunk`
+/// format; precompiled `.fzb` files carrying a different version are rejected
+/// at load time rather than fed as garbage to `deserialize_chunk`.
+pub const FUSABI_BYTECODE_VERSION: u32 = 1;
+
+/// Metadata header written ahead of the bytecode in a processed `.fzb` file.
+///
+/// The on-disk layout is fixed at [`FusabiHeader::SIZE`] little-endian bytes
+/// (`magic`, `version`, `timestamp`) followed immediately by the serialized
+/// chunk.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct FusabiHeader {
     pub magic: u32,
     pub version: u32,
     pub timestamp: u64,
 }
 
+impl FusabiHeader {
+    /// Size of the encoded header in bytes.
+    pub const SIZE: usize = 16;
+
+    /// Build a header for the current bytecode version with the given compile
+    /// `timestamp` (seconds since the Unix epoch).
+    pub fn new(timestamp: u64) -> Self {
+        Self {
+            magic: FUSABI_MAGIC,
+            version: FUSABI_BYTECODE_VERSION,
+            timestamp,
+        }
+    }
+
+    /// Whether `bytes` begins with the [`FUSABI_MAGIC`] number, i.e. they are
+    /// processed bytecode carrying a header rather than source.
+    pub fn has_magic(bytes: &[u8]) -> bool {
+        bytes.len() >= 4
+            && u32::from_le_bytes(bytes[0..4].try_into().unwrap()) == FUSABI_MAGIC
+    }
+
+    /// Encode the header into its fixed little-endian byte layout.
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut out = [0u8; Self::SIZE];
+        out[0..4].copy_from_slice(&self.magic.to_le_bytes());
+        out[4..8].copy_from_slice(&self.version.to_le_bytes());
+        out[8..16].copy_from_slice(&self.timestamp.to_le_bytes());
+        out
+    }
+
+    /// Decode a header from the front of `bytes`, returning it alongside the
+    /// remaining bytecode slice.
+    pub fn read_from(bytes: &[u8]) -> Result<(Self, &[u8]), FusabiHeaderError> {
+        if bytes.len() < Self::SIZE {
+            return Err(FusabiHeaderError::Truncated { len: bytes.len() });
+        }
+        let magic = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+        if magic != FUSABI_MAGIC {
+            return Err(FusabiHeaderError::BadMagic { found: magic });
+        }
+        let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        let timestamp = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
+        let header = Self {
+            magic,
+            version,
+            timestamp,
+        };
+        Ok((header, &bytes[Self::SIZE..]))
+    }
+}
+
+/// Errors produced while decoding a [`FusabiHeader`].
+#[derive(thiserror::Error, Debug)]
+pub enum FusabiHeaderError {
+    #[error("bytecode is too short to contain a header: {len} bytes")]
+    Truncated { len: usize },
+    #[error("bad magic number: expected {:#010x}, found {found:#010x}", FUSABI_MAGIC)]
+    BadMagic { found: u32 },
+}
+
 impl FusabiScript {
     pub fn new(name: String, bytecode: Vec<u8>) -> Self {
         Self { name, bytecode }
@@ -37,4 +112,54 @@ impl FusabiScript {
         fusabi_vm::deserialize_chunk(&self.bytecode)
             .map_err(|e| e.to_string())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_round_trips() {
+        let header = FusabiHeader::new(0x0123_4567_89ab_cdef);
+        let mut encoded = header.to_bytes().to_vec();
+        encoded.extend_from_slice(b"bytecode");
+
+        let (decoded, body) = FusabiHeader::read_from(&encoded).unwrap();
+        assert_eq!(decoded, header);
+        assert_eq!(decoded.magic, FUSABI_MAGIC);
+        assert_eq!(decoded.version, FUSABI_BYTECODE_VERSION);
+        assert_eq!(body, b"bytecode");
+    }
+
+    #[test]
+    fn has_magic_distinguishes_bytecode_from_source() {
+        assert!(FusabiHeader::has_magic(&FusabiHeader::new(0).to_bytes()));
+        assert!(!FusabiHeader::has_magic(b"let x = 1"));
+        assert!(!FusabiHeader::has_magic(b"ab"));
+    }
+
+    #[test]
+    fn read_from_rejects_truncated_input() {
+        let err = FusabiHeader::read_from(&[0u8; 4]).unwrap_err();
+        assert!(matches!(err, FusabiHeaderError::Truncated { len: 4 }));
+    }
+
+    #[test]
+    fn read_from_rejects_bad_magic() {
+        let mut bytes = FusabiHeader::new(0).to_bytes();
+        bytes[0] ^= 0xff;
+        let err = FusabiHeader::read_from(&bytes).unwrap_err();
+        assert!(matches!(err, FusabiHeaderError::BadMagic { .. }));
+    }
+
+    #[test]
+    fn version_mismatch_is_detectable() {
+        // A header written by a future bytecode version still decodes, but its
+        // version no longer matches what this build links — the loader turns
+        // this into `FusabiLoaderError::IncompatibleVersion`.
+        let mut header = FusabiHeader::new(0);
+        header.version = FUSABI_BYTECODE_VERSION + 1;
+        let (decoded, _) = FusabiHeader::read_from(&header.to_bytes()).unwrap();
+        assert_ne!(decoded.version, FUSABI_BYTECODE_VERSION);
+    }
 }
\ No newline at end of file
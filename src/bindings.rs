@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+use fusabi_vm::{Value, Vm};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A value passed across the boundary between the Fusabi VM and native Rust
+/// host functions.
+///
+/// Fusabi's own [`Value`](fusabi_vm::Value) type is `!Send`/`!Sync` (it is
+/// built on `Rc`), so we marshal to and from this owned, self-contained enum
+/// whenever a script calls a registered host function. It intentionally covers
+/// only the handful of shapes that round-trip cleanly between the two worlds.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ScriptValue {
+    #[default]
+    Nil,
+    Bool(bool),
+    Number(f64),
+    Str(String),
+    List(Vec<ScriptValue>),
+    Map(HashMap<String, ScriptValue>),
+}
+
+impl From<&Value> for ScriptValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Nil => ScriptValue::Nil,
+            Value::Bool(b) => ScriptValue::Bool(*b),
+            Value::Number(n) => ScriptValue::Number(*n),
+            Value::Str(s) => ScriptValue::Str(s.to_string()),
+            Value::List(items) => {
+                ScriptValue::List(items.iter().map(ScriptValue::from).collect())
+            }
+            Value::Map(entries) => ScriptValue::Map(
+                entries
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), ScriptValue::from(v)))
+                    .collect(),
+            ),
+            // Functions and other internal values have no host representation.
+            _ => ScriptValue::Nil,
+        }
+    }
+}
+
+impl From<ScriptValue> for Value {
+    fn from(value: ScriptValue) -> Self {
+        match value {
+            ScriptValue::Nil => Value::Nil,
+            ScriptValue::Bool(b) => Value::Bool(b),
+            ScriptValue::Number(n) => Value::Number(n),
+            ScriptValue::Str(s) => Value::from_string(s),
+            ScriptValue::List(items) => Value::from_list(items.into_iter().map(Value::from).collect()),
+            ScriptValue::Map(entries) => Value::from_map(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, Value::from(v)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// A native function a script can call. It receives exclusive access to the
+/// [`World`] (so it can spawn entities, touch components by name, or emit
+/// events) along with the arguments the script passed.
+pub type HostFn = dyn Fn(&mut World, Vec<ScriptValue>) -> ScriptValue + Send + Sync;
+
+/// Registry of native functions exposed to Fusabi scripts.
+///
+/// Populate it at startup with [`register`](Self::register) — usually through
+/// the [`RegisterFusabiFn::register_fusabi_fn`] app extension — and the runner
+/// injects every entry into a VM's global environment before it executes a
+/// chunk.
+#[derive(Resource, Default)]
+pub struct FusabiHostApi {
+    functions: HashMap<String, Arc<HostFn>>,
+}
+
+impl FusabiHostApi {
+    /// Register a named host function, overwriting any previous binding.
+    pub fn register<F>(&mut self, name: impl Into<String>, f: F)
+    where
+        F: Fn(&mut World, Vec<ScriptValue>) -> ScriptValue + Send + Sync + 'static,
+    {
+        self.functions.insert(name.into(), Arc::new(f));
+    }
+
+    /// The names of every registered function, in arbitrary order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.functions.keys().map(String::as_str)
+    }
+
+    /// Bind every registered function into `vm`'s global environment.
+    ///
+    /// Each bound global captures a shared handle to its function, so it calls
+    /// straight through [`with_world`] without touching the [`FusabiHostApi`]
+    /// resource at call time — this keeps re-entrant script→host→script calls
+    /// from removing the resource out from under themselves. The globals only
+    /// work while a [`WorldScope`] is active on the current thread, which the
+    /// runner guarantees for the duration of `Vm::execute`.
+    pub fn install(&self, vm: &mut Vm) {
+        for (name, function) in &self.functions {
+            let function = Arc::clone(function);
+            vm.set_global(
+                name,
+                Value::native_fn(move |args: Vec<Value>| {
+                    let args = args.iter().map(ScriptValue::from).collect();
+                    let result = with_world(|world| function(world, args)).unwrap_or_default();
+                    Value::from(result)
+                }),
+            );
+        }
+    }
+}
+
+thread_local! {
+    /// Raw pointer to the `World` that is currently driving a VM on this thread.
+    ///
+    /// The VM is `!Send`, so scripts always run on the main thread inside a
+    /// [`WorldScope`]; the pointer is only ever read back out while that scope
+    /// is live, giving the host closures a way to reach the `World` that the
+    /// borrow checker cannot thread through the VM's `'static` callbacks.
+    static ACTIVE_WORLD: Cell<*mut World> = const { Cell::new(std::ptr::null_mut()) };
+}
+
+/// RAII guard that publishes a `World` pointer for host functions to use.
+///
+/// Create one immediately before `Vm::execute` and drop it straight after; the
+/// guard restores whatever pointer it replaced, so nested execution is safe.
+pub struct WorldScope {
+    previous: *mut World,
+}
+
+impl WorldScope {
+    /// Publish `world` as the active world for the lifetime of the guard.
+    pub fn new(world: &mut World) -> Self {
+        let previous = ACTIVE_WORLD.with(|cell| cell.replace(world as *mut World));
+        Self { previous }
+    }
+}
+
+impl Drop for WorldScope {
+    fn drop(&mut self) {
+        ACTIVE_WORLD.with(|cell| cell.set(self.previous));
+    }
+}
+
+/// Run `f` with the world published by the innermost active [`WorldScope`].
+///
+/// Returns `None` when called outside a scope, or when called re-entrantly
+/// while an outer host call already holds the world — a host function that
+/// triggers another script cannot also borrow the world, so the nested call
+/// sees no world rather than aliasing the outer `&mut World`.
+fn with_world<R>(f: impl FnOnce(&mut World) -> R) -> Option<R> {
+    // Take the pointer for the duration of the call so a re-entrant invocation
+    // finds the slot empty instead of handing out a second `&mut World`.
+    let ptr = ACTIVE_WORLD.with(|cell| cell.replace(std::ptr::null_mut()));
+    if ptr.is_null() {
+        return None;
+    }
+    // SAFETY: the pointer was published by a live `WorldScope` that holds the
+    // only `&mut World` for the current (main) thread and outlives this call;
+    // the VM is single-threaded and we cleared the slot above, so no other
+    // access can alias it while `f` runs.
+    let result = f(unsafe { &mut *ptr });
+    ACTIVE_WORLD.with(|cell| cell.set(ptr));
+    Some(result)
+}
+
+/// Extension trait for registering Fusabi host functions on the [`App`].
+pub trait RegisterFusabiFn {
+    /// Register a native function callable from scripts by `name`.
+    fn register_fusabi_fn<F>(&mut self, name: impl Into<String>, f: F) -> &mut Self
+    where
+        F: Fn(&mut World, Vec<ScriptValue>) -> ScriptValue + Send + Sync + 'static;
+}
+
+impl RegisterFusabiFn for App {
+    fn register_fusabi_fn<F>(&mut self, name: impl Into<String>, f: F) -> &mut Self
+    where
+        F: Fn(&mut World, Vec<ScriptValue>) -> ScriptValue + Send + Sync + 'static,
+    {
+        self.world_mut()
+            .get_resource_or_insert_with(FusabiHostApi::default)
+            .register(name, f);
+        self
+    }
+}
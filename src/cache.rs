@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use crate::asset::FusabiScript;
+use fusabi_vm::Chunk;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Cache of deserialized [`Chunk`]s keyed by [`AssetId`].
+///
+/// `FusabiScript::to_chunk` is expensive — it runs `deserialize_chunk` over the
+/// stored bytecode — so the runner would otherwise pay that cost on every
+/// entity, every frame. This resource deserializes a script once and hands out
+/// shared [`Rc`] references; entries are dropped when the source asset changes.
+///
+/// `Chunk` (and `Rc`) are `!Send`, so this is a main-thread-only non-send
+/// resource, accessed exclusively from [`run_scripts`](crate::runner).
+#[derive(Default)]
+pub struct ChunkCache {
+    chunks: HashMap<AssetId<FusabiScript>, Rc<Chunk>>,
+}
+
+impl ChunkCache {
+    /// Return the cached chunk for `id`, if one has been deserialized.
+    pub fn get(&self, id: AssetId<FusabiScript>) -> Option<Rc<Chunk>> {
+        self.chunks.get(&id).cloned()
+    }
+
+    /// Store a freshly deserialized chunk under `id`.
+    pub fn insert(&mut self, id: AssetId<FusabiScript>, chunk: Rc<Chunk>) {
+        self.chunks.insert(id, chunk);
+    }
+
+    /// Drop the cached chunk for `id`, forcing the next access to deserialize.
+    pub fn invalidate(&mut self, id: AssetId<FusabiScript>) {
+        self.chunks.remove(&id);
+    }
+}
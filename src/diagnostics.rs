@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use crate::bindings::ScriptValue;
+
+/// A typed script failure surfaced by the [`runner`](crate::runner) while
+/// turning a loaded script into a running behavior.
+///
+/// Source-stage failures (lexing, parsing, compiling) happen inside
+/// [`FusabiLoader`](crate::loader) and are reported through Bevy's asset
+/// system as [`FusabiLoaderError`](crate::loader::FusabiLoaderError); the
+/// variants here cover the failures the runner itself observes.
+#[derive(Debug, Clone)]
+pub enum ScriptError {
+    /// The stored bytecode could not be deserialized into a chunk.
+    Serialization(String),
+    /// A chunk or one of its entry functions failed while executing.
+    Runtime(String),
+}
+
+/// Emitted by the runner whenever a loaded script's bytecode fails to
+/// deserialize or the script fails while running. Lexer, parser and compiler
+/// failures are not sent as this event.
+///
+/// Carries the offending entity (when the failure happened while running a
+/// behavior) and the script name so systems can match on and react to script
+/// problems instead of scraping stdout.
+#[derive(Event, Debug, Clone)]
+pub struct ScriptDiagnostic {
+    pub entity: Option<Entity>,
+    pub script: String,
+    pub error: ScriptError,
+}
+
+/// The latest result of running a script, updated on the entity when the
+/// script is initialized and each time one of its entry points runs.
+#[derive(Component, Debug, Clone, Default)]
+pub enum ScriptOutcome {
+    /// The script is loaded and its lifecycle entry points are being driven.
+    #[default]
+    Running,
+    /// The last entry point returned this value.
+    Succeeded(ScriptValue),
+    /// The last entry point failed with this error.
+    Failed(ScriptError),
+}
@@ -1,22 +1,79 @@
 pub mod asset;
+pub mod bindings;
+pub mod cache;
+pub mod diagnostics;
 pub mod loader;
+pub mod registry;
 pub mod runner;
+pub mod saver;
 
+use bevy::asset::processor::LoadAndSave;
 use bevy::prelude::*;
 use asset::FusabiScript;
 use loader::FusabiLoader;
+use registry::ScriptRegistry;
+use saver::FusabiSaver;
 
-pub struct FusabiPlugin;
+/// Core plugin: registers the [`FusabiScript`] asset, its loader and `.fzb`
+/// processor, and — unless disabled — loads a folder of scripts into a
+/// [`ScriptRegistry`] for name-based lookup.
+pub struct FusabiPlugin {
+    /// Asset-root-relative folder to load scripts from and index by name.
+    /// `None` disables folder loading.
+    pub scripts_folder: Option<String>,
+}
+
+impl Default for FusabiPlugin {
+    fn default() -> Self {
+        Self {
+            scripts_folder: Some("scripts".to_string()),
+        }
+    }
+}
+
+impl FusabiPlugin {
+    /// Load and index scripts from `folder` (relative to the asset root).
+    pub fn with_scripts_folder(folder: impl Into<String>) -> Self {
+        Self {
+            scripts_folder: Some(folder.into()),
+        }
+    }
+
+    /// Disable folder loading; scripts must be loaded and indexed by hand.
+    pub fn without_scripts_folder() -> Self {
+        Self {
+            scripts_folder: None,
+        }
+    }
+}
 
 impl Plugin for FusabiPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<FusabiScript>()
-           .init_asset_loader::<FusabiLoader>();
+           .init_asset_loader::<FusabiLoader>()
+           .init_resource::<ScriptRegistry>()
+           // Compile `.fsx` sources once and cache them as versioned `.fzb`
+           // bytecode, so later startups load processed assets directly.
+           .register_asset_processor::<LoadAndSave<FusabiLoader, FusabiSaver>>(
+               FusabiSaver.into(),
+           )
+           .set_default_asset_processor::<LoadAndSave<FusabiLoader, FusabiSaver>>("fsx");
+
+        if let Some(folder) = self.scripts_folder.clone() {
+            app.add_systems(Startup, move |asset_server: Res<AssetServer>, mut registry: ResMut<ScriptRegistry>| {
+                registry.set_folder(asset_server.load_folder(folder.clone()));
+            });
+            app.add_systems(Update, registry::index_scripts);
+        }
     }
 }
 
 pub mod prelude {
     pub use crate::asset::FusabiScript;
+    pub use crate::bindings::{FusabiHostApi, RegisterFusabiFn, ScriptValue};
+    pub use crate::cache::ChunkCache;
+    pub use crate::diagnostics::{ScriptDiagnostic, ScriptError, ScriptOutcome};
+    pub use crate::registry::ScriptRegistry;
     pub use crate::runner::{RunScript, RunnerPlugin};
     pub use crate::FusabiPlugin;
-}
\ No newline at end of file
+}
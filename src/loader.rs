@@ -1,4 +1,4 @@
-use crate::asset::FusabiScript;
+use crate::asset::{FusabiHeader, FusabiHeaderError, FusabiScript, FUSABI_BYTECODE_VERSION};
 use bevy::asset::{AssetLoader, LoadContext, io::Reader};
 use bevy::prelude::*;
 use fusabi_frontend::{Compiler, Lexer, Parser};
@@ -23,6 +23,10 @@ pub enum FusabiLoaderError {
     Bytecode(String),
     #[error("UTF-8 encoding error: {0}")]
     Utf8(#[from] std::string::FromUtf8Error),
+    #[error("Malformed bytecode header: {0}")]
+    Header(#[from] FusabiHeaderError),
+    #[error("Incompatible bytecode version: found {found}, expected {expected}")]
+    IncompatibleVersion { found: u32, expected: u32 },
 }
 
 impl AssetLoader for FusabiLoader {
@@ -36,7 +40,6 @@ impl AssetLoader for FusabiLoader {
         _settings: &Self::Settings,
         load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
-        let ext = load_context.path().extension().and_then(|s| s.to_str()).unwrap_or("");
         let name = load_context
             .path()
             .file_stem()
@@ -47,11 +50,22 @@ impl AssetLoader for FusabiLoader {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
 
-        let bytecode = if ext == "fzb" {
-            // It's already bytecode
-            bytes
+        // Dispatch on the magic number, not the extension: the asset processor
+        // runs this loader over header-prefixed bytecode whose path still ends
+        // in `.fsx`, so only the header itself reliably tells source from
+        // processed bytecode.
+        let bytecode = if FusabiHeader::has_magic(&bytes) {
+            // Processed bytecode: validate the header before trusting the body.
+            let (header, body) = FusabiHeader::read_from(&bytes)?;
+            if header.version != FUSABI_BYTECODE_VERSION {
+                return Err(FusabiLoaderError::IncompatibleVersion {
+                    found: header.version,
+                    expected: FUSABI_BYTECODE_VERSION,
+                });
+            }
+            body.to_vec()
         } else {
-            // Compile from source (.fsx)
+            // Source `.fsx`: compile it and serialize the chunk.
             let source = String::from_utf8(bytes)?;
             let chunk = compile_source(&source)?;
             // Serialize to bytecode
@@ -0,0 +1,103 @@
+use bevy::asset::LoadedFolder;
+use bevy::prelude::*;
+use crate::asset::FusabiScript;
+use crate::runner::RunScript;
+use std::collections::HashMap;
+
+/// Index of loaded scripts by name, backed by a watched asset folder.
+///
+/// When [`FusabiPlugin`](crate::FusabiPlugin) is configured with a scripts
+/// folder it calls `asset_server.load_folder(..)` and indexes every contained
+/// [`FusabiScript`] here as the folder resolves, so callers can reach a script
+/// by name instead of tracking raw handles:
+///
+/// ```ignore
+/// registry.run_on(&mut commands, entity, "hello");
+/// ```
+#[derive(Resource, Default)]
+pub struct ScriptRegistry {
+    folder: Option<Handle<LoadedFolder>>,
+    by_name: HashMap<String, Handle<FusabiScript>>,
+}
+
+impl ScriptRegistry {
+    /// Record the folder handle whose contents should be indexed.
+    pub fn set_folder(&mut self, handle: Handle<LoadedFolder>) {
+        self.folder = Some(handle);
+    }
+
+    /// Look up a script handle by name.
+    pub fn get(&self, name: &str) -> Option<Handle<FusabiScript>> {
+        self.by_name.get(name).cloned()
+    }
+
+    /// The names of every indexed script, in arbitrary order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.by_name.keys().map(String::as_str)
+    }
+
+    /// Build a [`RunScript`] component for the named script, if it is indexed.
+    pub fn runner(&self, name: &str) -> Option<RunScript> {
+        self.get(name).map(RunScript::new)
+    }
+
+    /// Attach the named script to `entity`, returning `false` if no such script
+    /// is indexed.
+    pub fn run_on(&self, commands: &mut Commands, entity: Entity, name: &str) -> bool {
+        match self.runner(name) {
+            Some(runner) => {
+                commands.entity(entity).insert(runner);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Spawn a fresh entity running the named script, returning its id.
+    pub fn spawn(&self, commands: &mut Commands, name: &str) -> Option<Entity> {
+        self.runner(name).map(|runner| commands.spawn(runner).id())
+    }
+}
+
+/// Keep the [`ScriptRegistry`] in sync with its watched folder.
+///
+/// Re-indexes from the folder once it (or any contained script) loads, and
+/// prunes entries whose script asset was removed.
+pub(crate) fn index_scripts(
+    mut registry: ResMut<ScriptRegistry>,
+    folders: Res<Assets<LoadedFolder>>,
+    scripts: Res<Assets<FusabiScript>>,
+    mut folder_events: EventReader<AssetEvent<LoadedFolder>>,
+    mut script_events: EventReader<AssetEvent<FusabiScript>>,
+) {
+    let folder_id = registry.folder.as_ref().map(Handle::id);
+
+    let mut reindex = false;
+    for event in folder_events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } = event {
+            if Some(*id) == folder_id {
+                reindex = true;
+            }
+        }
+    }
+    for event in script_events.read() {
+        match event {
+            AssetEvent::Added { .. } | AssetEvent::Modified { .. } => reindex = true,
+            AssetEvent::Removed { id } => registry.by_name.retain(|_, handle| handle.id() != *id),
+            _ => {}
+        }
+    }
+
+    if reindex {
+        if let Some(folder) = folder_id.and_then(|id| folders.get(id)) {
+            for handle in &folder.handles {
+                let Ok(handle) = handle.clone().try_typed::<FusabiScript>() else {
+                    continue;
+                };
+                if let Some(script) = scripts.get(&handle) {
+                    registry.by_name.insert(script.name.clone(), handle);
+                }
+            }
+        }
+    }
+}
@@ -1,57 +1,216 @@
+use bevy::ecs::system::SystemState;
 use bevy::prelude::*;
+use crate::bindings::{FusabiHostApi, ScriptValue, WorldScope};
+use crate::cache::ChunkCache;
+use crate::diagnostics::{ScriptDiagnostic, ScriptError, ScriptOutcome};
 use crate::prelude::*;
-use fusabi_vm::Vm;
+use fusabi_vm::{Value, Vm};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 
 pub struct RunnerPlugin;
 
 impl Plugin for RunnerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, run_scripts);
+        app.init_resource::<FusabiHostApi>()
+           .add_event::<ScriptDiagnostic>()
+           .init_non_send_resource::<ScriptVms>()
+           .init_non_send_resource::<ChunkCache>()
+           .add_systems(Update, run_scripts);
     }
 }
 
+/// Attaches a Fusabi script to an entity as a persistent behavior.
+///
+/// Unlike a run-once program, a running script keeps a live [`Vm`] (held in the
+/// [`ScriptVms`] resource, since the VM is `!Send`) and is driven through named
+/// lifecycle entry points: `on_start` is called once when the script first
+/// loads, and `on_update(dt)` is called every frame thereafter. Editing the
+/// source re-runs `on_start` via asset hot-reloading.
 #[derive(Component)]
 pub struct RunScript {
     pub handle: Handle<FusabiScript>,
-    pub executed: bool,
 }
 
+impl RunScript {
+    pub fn new(handle: Handle<FusabiScript>) -> Self {
+        Self { handle }
+    }
+}
+
+/// Live VMs for running scripts, keyed by entity.
+///
+/// `fusabi_vm::Vm` is `!Send`/`!Sync`, so it cannot live in a component; this
+/// non-send resource owns the per-entity VMs and is only touched by the
+/// main-thread [`run_scripts`] exclusive system.
+#[derive(Default)]
+pub struct ScriptVms {
+    vms: HashMap<Entity, ScriptVm>,
+}
+
+struct ScriptVm {
+    vm: Vm,
+    name: String,
+    started: bool,
+    source: AssetId<FusabiScript>,
+}
+
+/// Drive every running script through its lifecycle entry points.
+///
+/// Runs as an exclusive system: the VMs are `!Send` and the host functions they
+/// call need exclusive `World` access. On `AssetEvent::Modified`/`Removed` the
+/// affected VMs and cached chunks are dropped; a modified script is then
+/// re-initialized from fresh bytecode and `on_start` runs again — giving live
+/// script editing without a restart.
 fn run_scripts(
-    mut query: Query<&mut RunScript>,
-    scripts: Res<Assets<FusabiScript>>,
+    world: &mut World,
+    mut events: Local<SystemState<EventReader<'static, 'static, AssetEvent<FusabiScript>>>>,
 ) {
-    for mut runner in query.iter_mut() {
-        if runner.executed {
-            continue;
+    // Invalidate VMs whose source changed or was removed.
+    let mut stale: Vec<AssetId<FusabiScript>> = Vec::new();
+    {
+        let mut reader = events.get_mut(world);
+        for event in reader.read() {
+            match event {
+                AssetEvent::Modified { id } | AssetEvent::Removed { id } => stale.push(*id),
+                _ => {}
+            }
+        }
+    }
+    if !stale.is_empty() {
+        world
+            .non_send_resource_mut::<ScriptVms>()
+            .vms
+            .retain(|_, sv| !stale.contains(&sv.source));
+        let mut cache = world.non_send_resource_mut::<ChunkCache>();
+        for id in &stale {
+            cache.invalidate(*id);
+        }
+    }
+
+    let dt = world.resource::<Time>().delta_seconds() as f64;
+
+    let pending: Vec<(Entity, Handle<FusabiScript>)> = world
+        .query::<(Entity, &RunScript)>()
+        .iter(world)
+        .map(|(entity, runner)| (entity, runner.handle.clone()))
+        .collect();
+
+    let live: HashSet<Entity> = pending.iter().map(|(entity, _)| *entity).collect();
+
+    for (entity, handle) in pending {
+        // Take the VM out of the map so host functions can borrow the world
+        // while it runs; it is reinserted once the frame's work is done.
+        let mut state = match world.non_send_resource_mut::<ScriptVms>().vms.remove(&entity) {
+            Some(state) => state,
+            None => match initialize(world, entity, &handle) {
+                Some(state) => state,
+                None => continue,
+            },
+        };
+
+        if !state.started {
+            invoke(world, entity, &mut state, "on_start", Vec::new());
+            state.started = true;
+        } else {
+            invoke(world, entity, &mut state, "on_update", vec![Value::Number(dt)]);
         }
 
-        // We need to clone the handle to use it for lookup, as we can't borrow from runner while mutating it
-        // actually we can just use &runner.handle
-        if let Some(script) = scripts.get(&runner.handle) {
-            println!("Executing script: {}", script.name);
-            
-            // Deserialize chunk
-            match script.to_chunk() {
+        world
+            .non_send_resource_mut::<ScriptVms>()
+            .vms
+            .insert(entity, state);
+    }
+
+    // Forget VMs whose entity (or `RunScript` component) is gone.
+    world
+        .non_send_resource_mut::<ScriptVms>()
+        .vms
+        .retain(|entity, _| live.contains(entity));
+}
+
+/// Build and prime a VM for `handle`, running the chunk's top level so its
+/// entry functions are defined. Returns `None` if the asset isn't ready; load
+/// and runtime failures are reported as [`ScriptDiagnostic`] events.
+fn initialize(world: &mut World, entity: Entity, handle: &Handle<FusabiScript>) -> Option<ScriptVm> {
+    let id = handle.id();
+    let name = world
+        .resource::<Assets<FusabiScript>>()
+        .get(handle)?
+        .name
+        .clone();
+
+    // Deserialize through the cache: the first running entity pays for it, and
+    // every later entity (and frame) reuses the shared chunk.
+    let chunk = match world.non_send_resource::<ChunkCache>().get(id) {
+        Some(chunk) => chunk,
+        None => {
+            let result = world.resource::<Assets<FusabiScript>>().get(handle)?.to_chunk();
+            match result {
                 Ok(chunk) => {
-                    // Create VM (thread-local or on-demand)
-                    let mut vm = Vm::new();
-                    
-                    // Execute
-                    match vm.execute(chunk) {
-                        Ok(value) => {
-                            println!("Script execution result: {:?}", value);
-                            runner.executed = true;
-                        }
-                        Err(e) => {
-                            println!("Script execution failed: {:?}", e);
-                            // Retry? Or mark failed?
-                        }
-                    }
+                    let chunk = Rc::new(chunk);
+                    world.non_send_resource_mut::<ChunkCache>().insert(id, chunk.clone());
+                    chunk
                 }
                 Err(e) => {
-                    println!("Failed to load chunk: {}", e);
+                    report(world, entity, &name, ScriptError::Serialization(e));
+                    return None;
                 }
             }
         }
+    };
+
+    let mut vm = Vm::new();
+    world.resource_scope(|_world, api: Mut<FusabiHostApi>| api.install(&mut vm));
+
+    // Running the top level defines the script's globals (including its entry
+    // functions) in the VM before we look them up. The clone is cheap — `Chunk`
+    // is `Rc`-backed — and leaves the cached copy intact for the next entity.
+    let scope = WorldScope::new(world);
+    let result = vm.execute((*chunk).clone());
+    drop(scope);
+    if let Err(e) = result {
+        report(world, entity, &name, ScriptError::Runtime(format!("{:?}", e)));
+        return None;
+    }
+
+    set_outcome(world, entity, ScriptOutcome::Running);
+    Some(ScriptVm {
+        vm,
+        name,
+        started: false,
+        source: id,
+    })
+}
+
+/// Call a lifecycle entry function by name if the script defines it, recording
+/// the result on the entity's [`ScriptOutcome`] and reporting failures.
+fn invoke(world: &mut World, entity: Entity, state: &mut ScriptVm, entry: &str, args: Vec<Value>) {
+    if !state.vm.has_global(entry) {
+        return;
+    }
+    let scope = WorldScope::new(world);
+    let result = state.vm.call(entry, args);
+    drop(scope);
+    match result {
+        Ok(value) => set_outcome(world, entity, ScriptOutcome::Succeeded(ScriptValue::from(&value))),
+        Err(e) => report(world, entity, &state.name, ScriptError::Runtime(format!("{:?}", e))),
+    }
+}
+
+/// Emit a diagnostic event and flag the entity's outcome as failed.
+fn report(world: &mut World, entity: Entity, script: &str, error: ScriptError) {
+    world.send_event(ScriptDiagnostic {
+        entity: Some(entity),
+        script: script.to_string(),
+        error: error.clone(),
+    });
+    set_outcome(world, entity, ScriptOutcome::Failed(error));
+}
+
+/// Update the [`ScriptOutcome`] component, if the entity still exists.
+fn set_outcome(world: &mut World, entity: Entity, outcome: ScriptOutcome) {
+    if let Some(mut entity) = world.get_entity_mut(entity) {
+        entity.insert(outcome);
     }
 }
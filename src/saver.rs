@@ -0,0 +1,48 @@
+use crate::asset::{FusabiHeader, FusabiScript};
+use crate::loader::FusabiLoader;
+use bevy::asset::io::{AsyncWriteExt, Writer};
+use bevy::asset::saver::{AssetSaver, SavedAsset};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Bakes a compiled [`FusabiScript`] into the versioned `.fzb` format.
+///
+/// Paired with [`FusabiLoader`] in the asset processing pipeline, this lets a
+/// source `.fsx` be compiled once and cached as processed bytecode that later
+/// startups read back without re-running the frontend.
+#[derive(Default)]
+pub struct FusabiSaver;
+
+/// Errors that can occur while writing a `.fzb` file.
+#[derive(Error, Debug)]
+pub enum FusabiSaverError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl AssetSaver for FusabiSaver {
+    type Asset = FusabiScript;
+    type Settings = ();
+    type OutputLoader = FusabiLoader;
+    type Error = FusabiSaverError;
+
+    async fn save(
+        &self,
+        writer: &mut Writer,
+        asset: SavedAsset<'_, Self::Asset>,
+        _settings: &Self::Settings,
+    ) -> Result<(), Self::Error> {
+        // Stamp the compile time into the header to record the bytecode's
+        // provenance. (This is wall-clock time, so the output is not
+        // byte-for-byte reproducible across builds.)
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let header = FusabiHeader::new(timestamp);
+        writer.write_all(&header.to_bytes()).await?;
+        writer.write_all(&asset.bytecode).await?;
+        Ok(())
+    }
+}